@@ -0,0 +1,134 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::state::{RbcRankedList, RbcState};
+use crate::RbcError;
+
+///
+/// Incremental/streaming builder for Rank-Biased Centroids fusion.
+///
+/// The top-level [`crate::rbc`] and [`crate::rbc_with_weights`] functions need every ranking
+/// collected up front. `RbcBuilder` instead lets runs be pushed in one at a time, e.g. as
+/// results stream in from several shards or remote search services, without forcing a
+/// `Vec<Vec<_>>` collection.
+///
+/// Named `RbcBuilder` rather than `Rbc`, since [`crate::Rbc`] is already taken by the
+/// [`crate::RankFusion`] implementor of the same name; use this type for incremental/streaming
+/// fusion and [`crate::Rbc`] for one-shot fusion through the [`crate::RankFusion`] trait.
+///
+/// # Example:
+///
+/// ```
+/// use rank_biased_centroids::RbcBuilder;
+///
+/// let mut builder = RbcBuilder::with_persistence(0.9).unwrap();
+/// builder.push_run(vec!['A', 'D', 'B', 'C', 'G', 'F']);
+/// builder.push_run(vec!['B', 'D', 'E', 'C']);
+/// builder.push_run(vec!['A', 'B', 'D', 'C', 'G', 'F', 'E']);
+/// builder.push_run(vec!['G', 'D', 'E', 'A', 'F', 'C']);
+/// let res = builder.finish();
+/// let exp = vec!['D', 'C', 'A', 'B', 'G', 'E', 'F'];
+/// assert!(res.into_ranked_list().into_iter().eq(exp.into_iter()));
+/// ```
+///
+/// With attribution enabled, each item's fused score can be broken down into the runs that
+/// contributed to it:
+///
+/// ```
+/// use rank_biased_centroids::RbcBuilder;
+///
+/// let mut builder = RbcBuilder::with_persistence_and_attribution(0.9).unwrap();
+/// builder.push_run(vec!['A', 'D', 'B']);
+/// builder.push_run(vec!['B', 'D', 'E']);
+/// let res = builder.finish();
+///
+/// let d = res
+///     .into_ranked_list_with_attribution()
+///     .into_iter()
+///     .find(|(item, _, _)| *item == 'D')
+///     .unwrap();
+/// // 'D' appeared in both runs, so it has two contributions.
+/// assert_eq!(d.2.len(), 2);
+/// ```
+///
+#[derive(Debug)]
+pub struct RbcBuilder<Item: Eq + Hash + Debug> {
+    state: RbcState<Item>,
+    next_run: usize,
+}
+
+impl<Item: Eq + Hash + Debug> RbcBuilder<Item> {
+    ///
+    /// Start a new incremental fusion with persistence `p`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return `Err` if `p` is not 0 <= p < 1
+    ///
+    pub fn with_persistence(persistence: f64) -> Result<Self, RbcError> {
+        Ok(Self {
+            state: RbcState::with_persistence(persistence)?,
+            next_run: 0,
+        })
+    }
+
+    ///
+    /// Like [`Self::with_persistence`], but additionally records each item's per-run
+    /// contributions so [`RbcRankedList::into_ranked_list_with_attribution`] can explain why one
+    /// item outranks another (e.g. broad agreement across runs versus one dominant run).
+    ///
+    /// # Errors
+    ///
+    /// - Will return `Err` if `p` is not 0 <= p < 1
+    ///
+    pub fn with_persistence_and_attribution(persistence: f64) -> Result<Self, RbcError> {
+        let mut builder = Self::with_persistence(persistence)?;
+        builder.state.enable_attribution();
+        Ok(builder)
+    }
+
+    /// Push the next run unweighted, i.e. with a run weight of 1.0.
+    pub fn push_run<I>(&mut self, ranked_list: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        self.state.register_run(self.next_run, None);
+        for (rank, item) in ranked_list.into_iter().enumerate() {
+            self.state.update(self.next_run, rank, item, None);
+        }
+        self.next_run += 1;
+        self
+    }
+
+    ///
+    /// Push the next run, scaling its contribution by `run_weight`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return `Err` if `run_weight` is infinite or NaN
+    ///
+    pub fn push_weighted_run<I>(
+        &mut self,
+        ranked_list: I,
+        run_weight: f64,
+    ) -> Result<&mut Self, RbcError>
+    where
+        I: IntoIterator<Item = Item>,
+    {
+        if run_weight.is_infinite() || run_weight.is_nan() {
+            return Err(RbcError::InvalidRunWeights);
+        }
+        self.state.register_run(self.next_run, Some(run_weight));
+        for (rank, item) in ranked_list.into_iter().enumerate() {
+            self.state.update(self.next_run, rank, item, Some(run_weight));
+        }
+        self.next_run += 1;
+        Ok(self)
+    }
+
+    /// Finalize the incremental fusion, consuming the builder, and return the fused ranked list.
+    #[must_use]
+    pub fn finish(self) -> RbcRankedList<Item> {
+        self.state.into_result()
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::state::{self, RbcRankedList};
+use crate::RbcError;
+
+///
+/// A pluggable rank fusion method: consumes one ranking per input run (optionally scaled by a
+/// per-run weight) and produces a fused [`RbcRankedList`].
+///
+/// This lets callers benchmark different fusion methods against each other through a single
+/// call site while reusing the same output type and run-weight handling. [`Rbc`] is the default
+/// implementor; [`ReciprocalRankFusion`], [`Borda`] and [`CombSum`] are provided as alternatives.
+///
+/// # Example:
+///
+/// ```
+/// use rank_biased_centroids::{RankFusion, Rbc, ReciprocalRankFusion};
+///
+/// let r1 = vec!['A', 'D', 'B', 'C'];
+/// let r2 = vec!['B', 'D', 'E', 'C'];
+///
+/// let rbc = Rbc::new(0.9).unwrap();
+/// let rrf = ReciprocalRankFusion::default();
+///
+/// let rbc_result = rbc.fuse(vec![r1.clone(), r2.clone()]).unwrap();
+/// let rrf_result = rrf.fuse(vec![r1, r2]).unwrap();
+/// // Both methods pick 'B' here (ranked 3rd then 1st) over 'D' (ranked 2nd in both runs), but
+/// // they need not agree in general: RRF has no concept of RBC's geometric persistence.
+/// assert_eq!(rbc_result.into_ranked_list()[0], 'B');
+/// assert_eq!(rrf_result.into_ranked_list()[0], 'B');
+/// ```
+///
+pub trait RankFusion<Item: Eq + Hash + Debug> {
+    /// Fuse `input_rankings`, giving every run equal weight.
+    ///
+    /// # Errors
+    ///
+    /// Implementors return `Err` if their configuration (e.g. persistence) is invalid.
+    fn fuse<I>(&self, input_rankings: I) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>;
+
+    /// Fuse `input_rankings`, scaling each run's contribution by the matching entry in
+    /// `run_weights`.
+    ///
+    /// # Errors
+    ///
+    /// Implementors return `Err` if `run_weights` doesn't have one entry per run, if any weight
+    /// is infinite or NaN, or if their configuration (e.g. persistence) is invalid.
+    fn fuse_with_weights<I>(
+        &self,
+        input_rankings: I,
+        run_weights: impl IntoIterator<Item = f64>,
+    ) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>;
+}
+
+// Ranks and run lengths are tiny in any plausible input (nowhere near f64's 2^53 exact-integer
+// limit), so the precision loss this cast could in principle lose is not observable in practice.
+#[allow(clippy::cast_precision_loss)]
+fn as_f64(n: usize) -> f64 {
+    n as f64
+}
+
+// Shared accumulation loop for the rank-derived fusion methods below (everything except RBC,
+// which has its own state machine in `state::RbcState` to support the tail-residual bookkeeping).
+// `score(rank, run_len)` maps a 0-based rank and the length of the run it came from to that
+// run's raw contribution, before the run weight is applied.
+fn fuse_by_rank_score<Item, I>(
+    input_rankings: I,
+    run_weights: Option<Vec<f64>>,
+    score: impl Fn(usize, usize) -> f64,
+) -> Result<RbcRankedList<Item>, RbcError>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator<Item = Item>,
+    Item: Eq + Hash + Debug,
+{
+    let mut scores: HashMap<Item, f64> = HashMap::new();
+    let mut run_weights_iter = run_weights.map(IntoIterator::into_iter);
+    let mut rankings_iter = input_rankings.into_iter();
+
+    for ranked_list in rankings_iter.by_ref() {
+        let run_weight = match run_weights_iter.as_mut() {
+            None => 1.0,
+            Some(iter) => match iter.next() {
+                None => return Err(RbcError::InvalidRunWeights),
+                Some(w) if w.is_infinite() || w.is_nan() => {
+                    return Err(RbcError::InvalidRunWeights)
+                }
+                Some(w) => w,
+            },
+        };
+
+        let ranked_list: Vec<Item> = ranked_list.into_iter().collect();
+        let run_len = ranked_list.len();
+        for (rank, item) in ranked_list.into_iter().enumerate() {
+            let contribution = score(rank, run_len) * run_weight;
+            scores
+                .entry(item)
+                .and_modify(|s| *s += contribution)
+                .or_insert(contribution);
+        }
+    }
+
+    // more weights than runs (fewer weights than runs is caught in the loop above)
+    if let Some(mut iter) = run_weights_iter {
+        if iter.next().is_some() {
+            return Err(RbcError::InvalidRunWeights);
+        }
+    }
+
+    Ok(state::ranked_list_from_scores(scores.into_iter().collect()))
+}
+
+///
+/// Rank-Biased Centroids, the default [`RankFusion`] implementor. Thin wrapper around
+/// [`crate::rbc`] / [`crate::rbc_with_weights`] so RBC can be used through the same trait as the
+/// alternative fusion methods. For incremental/streaming fusion (runs pushed in one at a time),
+/// see [`crate::RbcBuilder`] instead.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rbc {
+    persistence: f64,
+}
+
+impl Rbc {
+    ///
+    /// Construct an RBC fusion method with persistence `p`.
+    ///
+    /// # Errors
+    ///
+    /// - Will return `Err` if `p` is not 0 <= p < 1
+    ///
+    pub fn new(persistence: f64) -> Result<Self, RbcError> {
+        state::validate_persistence(persistence)?;
+        Ok(Self { persistence })
+    }
+}
+
+impl<Item: Eq + Hash + Debug> RankFusion<Item> for Rbc {
+    fn fuse<I>(&self, input_rankings: I) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        crate::rbc(input_rankings, self.persistence)
+    }
+
+    fn fuse_with_weights<I>(
+        &self,
+        input_rankings: I,
+        run_weights: impl IntoIterator<Item = f64>,
+    ) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        // `rbc_with_weights` draws exactly one weight per run, so a weight still left over
+        // afterwards means there were more weights than runs.
+        let mut run_weights = run_weights.into_iter();
+        let result =
+            crate::rbc_with_weights(input_rankings, run_weights.by_ref(), self.persistence)?;
+        if run_weights.next().is_some() {
+            return Err(RbcError::InvalidRunWeights);
+        }
+        Ok(result)
+    }
+}
+
+///
+/// Reciprocal Rank Fusion: an item's contribution from a run is `1 / (k + rank)`, using a
+/// 1-based rank, summed (and optionally scaled by a run weight) across runs.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReciprocalRankFusion {
+    k: f64,
+}
+
+impl ReciprocalRankFusion {
+    /// Construct an RRF fusion method with constant `k`.
+    #[must_use]
+    pub fn new(k: f64) -> Self {
+        Self { k }
+    }
+}
+
+impl Default for ReciprocalRankFusion {
+    /// `k = 60`, the constant suggested in the original RRF paper.
+    fn default() -> Self {
+        Self { k: 60.0 }
+    }
+}
+
+impl<Item: Eq + Hash + Debug> RankFusion<Item> for ReciprocalRankFusion {
+    fn fuse<I>(&self, input_rankings: I) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(input_rankings, None, |rank, _run_len| {
+            1.0 / (self.k + as_f64(rank) + 1.0)
+        })
+    }
+
+    fn fuse_with_weights<I>(
+        &self,
+        input_rankings: I,
+        run_weights: impl IntoIterator<Item = f64>,
+    ) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(
+            input_rankings,
+            Some(run_weights.into_iter().collect()),
+            |rank, _run_len| 1.0 / (self.k + as_f64(rank) + 1.0),
+        )
+    }
+}
+
+///
+/// Borda count: an item's contribution from a run of length `n` is `n - rank` (a 0-based rank),
+/// summed (and optionally scaled by a run weight) across runs.
+///
+/// ```
+/// use rank_biased_centroids::{Borda, RankFusion};
+///
+/// let r1 = vec!['A', 'B', 'C', 'D'];
+/// let r2 = vec!['B', 'A', 'D', 'C'];
+/// let r3 = vec!['A', 'B', 'C', 'D'];
+/// let res = Borda.fuse(vec![r1, r2, r3]).unwrap();
+/// let exp = vec!['A', 'B', 'C', 'D'];
+/// assert!(res.into_ranked_list().into_iter().eq(exp.into_iter()));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Borda;
+
+impl<Item: Eq + Hash + Debug> RankFusion<Item> for Borda {
+    fn fuse<I>(&self, input_rankings: I) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(input_rankings, None, |rank, run_len| as_f64(run_len - rank))
+    }
+
+    fn fuse_with_weights<I>(
+        &self,
+        input_rankings: I,
+        run_weights: impl IntoIterator<Item = f64>,
+    ) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(
+            input_rankings,
+            Some(run_weights.into_iter().collect()),
+            |rank, run_len| as_f64(run_len - rank),
+        )
+    }
+}
+
+///
+/// `CombSUM` over rank-derived scores: each run's ranks are first normalized to `(n - rank) / n`
+/// so that runs of different lengths contribute comparably, then these per-run scores are
+/// summed (and optionally scaled by a run weight) across runs.
+///
+/// ```
+/// use rank_biased_centroids::{CombSum, RankFusion};
+///
+/// let r1 = vec!['A', 'B'];
+/// let r2 = vec!['B', 'A', 'C'];
+/// let res = CombSum.fuse(vec![r1, r2]).unwrap();
+/// let exp = vec!['A', 'B', 'C'];
+/// // Normalizing by run length lets 'A' (rank 0 of 2, rank 1 of 3) edge out 'B' (rank 1 of 2,
+/// // rank 0 of 3); unnormalized (i.e. plain `Borda`) the two would tie.
+/// assert!(res.into_ranked_list().into_iter().eq(exp.into_iter()));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CombSum;
+
+impl<Item: Eq + Hash + Debug> RankFusion<Item> for CombSum {
+    fn fuse<I>(&self, input_rankings: I) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(input_rankings, None, |rank, run_len| {
+            as_f64(run_len - rank) / as_f64(run_len)
+        })
+    }
+
+    fn fuse_with_weights<I>(
+        &self,
+        input_rankings: I,
+        run_weights: impl IntoIterator<Item = f64>,
+    ) -> Result<RbcRankedList<Item>, RbcError>
+    where
+        I: IntoIterator,
+        I::Item: IntoIterator<Item = Item>,
+    {
+        fuse_by_rank_score(
+            input_rankings,
+            Some(run_weights.into_iter().collect()),
+            |rank, run_len| as_f64(run_len - rank) / as_f64(run_len),
+        )
+    }
+}
@@ -1,24 +1,67 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 
 const VALID_P_RANGE: std::ops::Range<f64> = 0.0..1.0;
 
+// Checked in one place so the sequential and parallel entry points agree on what a valid
+// persistence value is.
+pub(crate) fn validate_persistence(persistence: f64) -> Result<(), crate::RbcError> {
+    if !VALID_P_RANGE.contains(&persistence) {
+        return Err(crate::RbcError::InvalidPersistance);
+    }
+    Ok(())
+}
+
+/// One run's contribution to an item's fused score: which run and rank it was found at, and how
+/// much weight that occurrence contributed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunContribution {
+    /// index of the run (0-based, in input order) this contribution came from
+    pub run: usize,
+    /// the rank (0-based) at which the item was found in that run
+    pub rank: usize,
+    /// the weight this occurrence contributed to the item's fused score
+    pub score: f64,
+}
+
+// Accumulated state for a single item: its summed weight, the set of runs it was seen in
+// (needed to work out which runs' unseen tails could still raise its score), and, when
+// attribution is enabled, the per-run contributions that made up `score`.
+#[derive(Debug)]
+struct ItemAccum {
+    score: f64,
+    runs_seen: HashSet<usize>,
+    contributions: Vec<RunContribution>,
+}
+
+#[derive(Debug)]
 pub(crate) struct RbcState<Item: Eq + Hash + Debug> {
     // precomputed weights for different ranks
     weights: Vec<f64>,
     // accumulated item weights
-    item_weights: HashMap<Item, f64>,
+    item_weights: HashMap<Item, ItemAccum>,
     // persitstence
     persistence: f64,
+    // (run_weight, run_length) for each run seen so far, indexed by run index
+    run_info: Vec<Option<(f64, usize)>>,
+    // when true, `update` also records per-run contributions for each item (opt-in, since most
+    // callers don't need the provenance and it costs an extra `Vec` push per occurrence)
+    attribution_enabled: bool,
 }
 
 impl<Item: Eq + Hash + Debug> RbcState<Item> {
     // Initialize the RBO state with persistance `p`
     pub(crate) fn with_persistence(persistence: f64) -> Result<Self, crate::RbcError> {
-        if !VALID_P_RANGE.contains(&persistence) {
-            return Err(crate::RbcError::InvalidPersistance);
-        }
+        validate_persistence(persistence)?;
+        Ok(Self::with_persistence_unchecked(persistence))
+    }
+
+    // Initialize the RBO state with persistance `p`, assuming it has already been validated.
+    // Used by the parallel entry points, which validate `p` once up front rather than once
+    // per per-thread state.
+    pub(crate) fn with_persistence_unchecked(persistence: f64) -> Self {
         let mut w = 1.0 - persistence;
         let weights = (0..10000i32)
             .map(|_| {
@@ -27,15 +70,34 @@ impl<Item: Eq + Hash + Debug> RbcState<Item> {
                 pw
             })
             .collect();
-        Ok(Self {
+        Self {
             persistence,
             weights,
             item_weights: HashMap::new(),
-        })
+            run_info: Vec::new(),
+            attribution_enabled: false,
+        }
+    }
+
+    // Opt in to recording per-run contributions for each item; see `RbcBuilder`.
+    pub(crate) fn enable_attribution(&mut self) {
+        self.attribution_enabled = true;
+    }
+
+    // Register a run's weight up front, independent of any items it contains, so a run with zero
+    // items still gets an entry in `run_info`: its full `run_weight` is unseen tail mass
+    // (`p^0 == 1`), and `update` is never called for it to create that entry otherwise. Callers
+    // invoke this once per run before (or instead of) looping over its items; `update` below
+    // reuses the same entry, so calling both for a non-empty run is harmless.
+    pub(crate) fn register_run(&mut self, run: usize, run_weight: Option<f64>) {
+        if self.run_info.len() <= run {
+            self.run_info.resize(run + 1, None);
+        }
+        self.run_info[run].get_or_insert((run_weight.unwrap_or(1.0), 0));
     }
 
     // Update the RBO state with two new elements.
-    pub(crate) fn update(&mut self, rank: usize, item: Item, run_weight: Option<f64>) {
+    pub(crate) fn update(&mut self, run: usize, rank: usize, item: Item, run_weight: Option<f64>) {
         while self.weights.len() <= rank {
             let last_weight = self.weights.last().expect("can't fail");
             let new_last = last_weight * self.persistence;
@@ -43,36 +105,234 @@ impl<Item: Eq + Hash + Debug> RbcState<Item> {
         }
         let w = *self.weights.get(rank).expect("this can't fail now");
         let weight = if let Some(rw) = run_weight { w * rw } else { w };
+
+        if self.run_info.len() <= run {
+            self.run_info.resize(run + 1, None);
+        }
+        let run_len = self.run_info[run].map_or(0, |(_, len)| len).max(rank + 1);
+        self.run_info[run] = Some((run_weight.unwrap_or(1.0), run_len));
+
+        let attribution_enabled = self.attribution_enabled;
         self.item_weights
             .entry(item)
-            .and_modify(|e| *e += weight)
-            .or_insert(weight);
+            .and_modify(|acc| {
+                acc.score += weight;
+                acc.runs_seen.insert(run);
+                if attribution_enabled {
+                    acc.contributions.push(RunContribution {
+                        run,
+                        rank,
+                        score: weight,
+                    });
+                }
+            })
+            .or_insert_with(|| {
+                let mut runs_seen = HashSet::new();
+                runs_seen.insert(run);
+                let contributions = if attribution_enabled {
+                    vec![RunContribution {
+                        run,
+                        rank,
+                        score: weight,
+                    }]
+                } else {
+                    Vec::new()
+                };
+                ItemAccum {
+                    score: weight,
+                    runs_seen,
+                    contributions,
+                }
+            });
     }
 
-    // we extrapolate the final RBO value and compute the residual
+    // Every run of length `L` leaves an unseen geometric tail of mass `p^L` (since
+    // `sum_{r>=L}(1-p)p^r = p^L`); an item absent from that run could still gain up to
+    // `run_weight * p^L` were it to appear somewhere below the observed cutoff. We compute that
+    // residual per run, and bound each item's final RBC value between its observed score and
+    // that score plus the residuals of every run it was not seen in.
     pub(crate) fn into_result(self) -> RbcRankedList<Item> {
-        let mut items: Vec<(Item, f64)> = self.item_weights.into_iter().collect();
+        let persistence = self.persistence;
+        let run_residuals: Vec<f64> = self
+            .run_info
+            .iter()
+            .map(|info| match info {
+                // `powi` takes an `i32`; saturate rather than truncate/wrap for the
+                // astronomically long run that would overflow it.
+                Some((run_weight, run_len)) => {
+                    let exponent = i32::try_from(*run_len).unwrap_or(i32::MAX);
+                    run_weight * persistence.powi(exponent)
+                }
+                None => 0.0,
+            })
+            .collect();
+        let overall_residual: f64 = run_residuals.iter().sum();
+
+        let mut items: Vec<(Item, f64, f64, Vec<RunContribution>)> = self
+            .item_weights
+            .into_iter()
+            .map(|(item, acc)| {
+                let residual_already_bounded: f64 =
+                    acc.runs_seen.iter().map(|&run| run_residuals[run]).sum();
+                let upper = acc.score + (overall_residual - residual_already_bounded);
+                (item, acc.score, upper, acc.contributions)
+            })
+            .collect();
         items.sort_by(|a, b| b.1.total_cmp(&a.1));
-        RbcRankedList { items }
+
+        RbcRankedList {
+            items,
+            run_residuals,
+        }
+    }
+
+    // Reduce step for the parallel entry points: sum two per-thread accumulators into one. Each
+    // per-thread state is built from a disjoint subset of runs, so `run_info` entries never
+    // collide; item scores and the runs they were seen in do need merging. `rayon` folds these
+    // merges in a different order than the sequential path sums occurrences, so resulting scores
+    // agree only up to floating-point reassociation, not bit-for-bit.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        if other.run_info.len() > self.run_info.len() {
+            self.run_info.resize(other.run_info.len(), None);
+        }
+        for (slot, info) in self.run_info.iter_mut().zip(other.run_info) {
+            if info.is_some() {
+                *slot = info;
+            }
+        }
+        for (item, mut other_acc) in other.item_weights {
+            match self.item_weights.get_mut(&item) {
+                Some(acc) => {
+                    acc.score += other_acc.score;
+                    acc.runs_seen.extend(other_acc.runs_seen);
+                    acc.contributions.append(&mut other_acc.contributions);
+                }
+                None => {
+                    self.item_weights.insert(item, other_acc);
+                }
+            }
+        }
+        self
+    }
+}
+
+// Build a ranked list directly from per-item scores, with no tail-residual information. Used by
+// the `RankFusion` implementors other than RBC itself, which don't model an unseen geometric
+// tail, so their lower and upper bounds coincide.
+pub(crate) fn ranked_list_from_scores<Item: Eq + Hash + Debug>(
+    mut items: Vec<(Item, f64)>,
+) -> RbcRankedList<Item> {
+    items.sort_by(|a, b| b.1.total_cmp(&a.1));
+    RbcRankedList {
+        items: items
+            .into_iter()
+            .map(|(item, score)| (item, score, score, Vec::new()))
+            .collect(),
+        run_residuals: Vec::new(),
     }
 }
 
-/// contains the rank fused list of items in order
+/// contains the rank fused list of items in order, together with the residual uncertainty left
+/// by the unseen tail of each input run
 #[derive(Debug)]
 pub struct RbcRankedList<Item: Eq + Hash + Debug> {
-    items: Vec<(Item, f64)>,
+    // (item, lower bound score, upper bound score, per-run contributions if attribution was
+    // enabled, else empty)
+    items: Vec<(Item, f64, f64, Vec<RunContribution>)>,
+    // tail residual mass contributed by each input run, in input order
+    run_residuals: Vec<f64>,
 }
 
 impl<Item: Eq + Hash + Debug> RbcRankedList<Item> {
     /// return the fused ranked list of items without scores
     #[must_use]
     pub fn into_ranked_list(self) -> Vec<Item> {
-        self.items.into_iter().map(|(item, _)| item).collect()
+        self.items.into_iter().map(|(item, ..)| item).collect()
     }
 
     /// return the fused ranked list of items with scores
     #[must_use]
     pub fn into_ranked_list_with_scores(self) -> Vec<(Item, f64)> {
         self.items
+            .into_iter()
+            .map(|(item, lower, ..)| (item, lower))
+            .collect()
+    }
+
+    /// return the fused ranked list of items, each with a lower and upper bound on its true
+    /// score. The lower bound is the score accumulated from observed occurrences; the upper
+    /// bound additionally assumes the item appears somewhere below the observed cutoff in every
+    /// run it was not observed in, i.e. it adds the run's entire unseen tail mass
+    /// (`run_weight * p^len`), not just the next unseen position. Two adjacent items are only
+    /// reliably ordered if their lower bounds differ by more than either item's residual
+    /// (`upper - lower`).
+    ///
+    /// ```
+    /// use rank_biased_centroids::rbc;
+    ///
+    /// // 'B' is absent from the second (shorter) run, so its true score could still be as high
+    /// // as 'A's, which was observed in every run and so carries no residual uncertainty.
+    /// let res = rbc(vec![vec!['A', 'B'], vec!['A']], 0.9).unwrap();
+    /// let bounds = res.into_ranked_list_with_bounds();
+    /// assert_eq!(bounds[0].0, 'A');
+    /// approx::assert_abs_diff_eq!(bounds[0].1, bounds[0].2, epsilon = 1e-9);
+    /// assert_eq!(bounds[1].0, 'B');
+    /// assert!(bounds[1].2 > bounds[0].1);
+    /// ```
+    #[must_use]
+    pub fn into_ranked_list_with_bounds(self) -> Vec<(Item, f64, f64)> {
+        self.items
+            .into_iter()
+            .map(|(item, lower, upper, _)| (item, lower, upper))
+            .collect()
+    }
+
+    /// return the fused ranked list of items with scores, each alongside the per-run
+    /// contributions that made up that score: which run and rank the item was found at, and how
+    /// much weight that occurrence added. Only populated when attribution was enabled (e.g. via
+    /// [`crate::RbcBuilder::with_persistence_and_attribution`]); otherwise every item's
+    /// contribution list is empty.
+    #[must_use]
+    pub fn into_ranked_list_with_attribution(self) -> Vec<(Item, f64, Vec<RunContribution>)> {
+        self.items
+            .into_iter()
+            .map(|(item, lower, _, contributions)| (item, lower, contributions))
+            .collect()
+    }
+
+    /// the tail residual mass contributed by each input run (`run_weight * p^len`), in input
+    /// order; the most additional score any single item absent from that run could still gain
+    ///
+    /// An empty run contributes its entire `run_weight` as residual (`p^0 == 1`): every item is
+    /// "unseen" in it.
+    ///
+    /// Empty for lists produced by the non-RBC [`crate::RankFusion`] implementors, which model no
+    /// unseen tail.
+    ///
+    /// ```
+    /// use rank_biased_centroids::rbc;
+    ///
+    /// let res = rbc(vec![vec!['A', 'B'], vec![], vec!['C', 'D']], 0.9).unwrap();
+    /// assert_eq!(res.run_residuals(), &[0.9f64.powi(2), 1.0, 0.9f64.powi(2)]);
+    /// ```
+    #[must_use]
+    pub fn run_residuals(&self) -> &[f64] {
+        &self.run_residuals
+    }
+
+    /// the total residual mass across all runs; the fused ordering of two items is only
+    /// guaranteed correct if their score gap exceeds this value
+    ///
+    /// ```
+    /// use rank_biased_centroids::rbc;
+    ///
+    /// // Residuals of 0.9^2, 1.0 and 0.9^2 (the empty run's full weight) sum to 2.62.
+    /// let res = rbc(vec![vec!['A', 'B'], vec![], vec!['C', 'D']], 0.9).unwrap();
+    /// approx::assert_abs_diff_eq!(res.overall_residual(), 2.62, epsilon = 1e-9);
+    /// ```
+    #[must_use]
+    pub fn overall_residual(&self) -> f64 {
+        self.run_residuals.iter().sum()
     }
 }